@@ -0,0 +1,400 @@
+//! Async driver built on `embedded-hal-async`
+
+use crate::{
+    register, Apds9306Type, Error, InterruptEvent, MeasurementData, Register, Sample, Status,
+    RESET_SETTLE_TIME_US,
+};
+use crate::{Config, InterruptConfig, I2C_ADDR};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::i2c::I2c;
+
+/// APDS-9306 async driver
+///
+/// `IRQ` is an optional `Wait`-capable input pin wired to the device's INT line, set with
+/// [`Self::with_interrupt_pin`]; it defaults to `()` for drivers that only poll status.
+pub struct Apds9306<I2C, D, IRQ = ()> {
+    /// I2C interface
+    i2c: I2C,
+    /// Delay provider, used to honor device timing
+    delay: D,
+    /// Current ALS configuration
+    config: Config,
+    /// Current interrupt configuration
+    interrupt_config: InterruptConfig,
+    /// Device variant, used for lux calibration
+    apds9306_type: Apds9306Type,
+    /// Calibration factor applied when converting raw counts to lux
+    #[cfg(feature = "out_f32")]
+    lux_factor: f32,
+    /// Input pin wired to the INT line, if any
+    irq: IRQ,
+}
+
+impl<I2C, D, E> Apds9306<I2C, D, ()>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+{
+    /// Creates a new APDS-9306 driver with default configuration and address
+    pub async fn new(i2c: I2C, delay: D, apds9306_type: Apds9306Type) -> Result<Self, Error<E>> {
+        let mut driver = Self {
+            i2c,
+            delay,
+            config: Config::default(),
+            interrupt_config: InterruptConfig::default(),
+            apds9306_type,
+            #[cfg(feature = "out_f32")]
+            lux_factor: apds9306_type.default_lux_factor(),
+            irq: (),
+        };
+
+        // verify device ID
+        driver.verify_device_id(apds9306_type).await?;
+
+        Ok(driver)
+    }
+
+    /// Attaches a `Wait`-capable input pin wired to the device's INT line, enabling
+    /// [`Self::wait_for_interrupt`]
+    pub fn with_interrupt_pin<IRQ>(self, irq: IRQ) -> Apds9306<I2C, D, IRQ>
+    where
+        IRQ: Wait,
+    {
+        Apds9306 {
+            i2c: self.i2c,
+            delay: self.delay,
+            config: self.config,
+            interrupt_config: self.interrupt_config,
+            apds9306_type: self.apds9306_type,
+            #[cfg(feature = "out_f32")]
+            lux_factor: self.lux_factor,
+            irq,
+        }
+    }
+}
+
+impl<I2C, D, IRQ, E> Apds9306<I2C, D, IRQ>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+{
+    /// Verifies the device ID
+    async fn verify_device_id(&mut self, apds9306_type: Apds9306Type) -> Result<(), Error<E>> {
+        let part_id = self.read_register(Register::PartId).await?;
+
+        // check if the part ID matches either APDS-9306 (0xB1) or APDS-9306-065 (0xB3)
+        match apds9306_type {
+            Apds9306Type::Apds9306 => {
+                if part_id != 0xB1 {
+                    return Err(Error::DeviceNotFound);
+                }
+            }
+            Apds9306Type::Apds9306_065 => {
+                if part_id != 0xB3 {
+                    return Err(Error::DeviceNotFound);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Performs a software reset
+    pub async fn reset(&mut self) -> Result<(), Error<E>> {
+        // set SW_Reset bit (bit 4) in MAIN_CTRL register
+        let value = register::main_ctrl::SW_RESET;
+        self.write_register(Register::MainCtrl, value).await?;
+
+        // wait for the reset to settle before the device is usable again
+        self.delay.delay_us(RESET_SETTLE_TIME_US).await;
+
+        Ok(())
+    }
+
+    /// Enables the sensor
+    pub async fn enable(&mut self) -> Result<(), Error<E>> {
+        // read current value
+        let current = self.read_register(Register::MainCtrl).await?;
+
+        // set ALS_EN bit (bit 1)
+        let value = current | register::main_ctrl::EN;
+        self.write_register(Register::MainCtrl, value).await?;
+
+        Ok(())
+    }
+
+    /// Disables the sensor
+    pub async fn disable(&mut self) -> Result<(), Error<E>> {
+        // read current value
+        let current = self.read_register(Register::MainCtrl).await?;
+
+        // clear ALS_EN bit (bit 1)
+        let value = current & !register::main_ctrl::EN;
+        self.write_register(Register::MainCtrl, value).await?;
+
+        Ok(())
+    }
+
+    /// Configures the sensor
+    pub async fn configure(&mut self, config: Config) -> Result<(), Error<E>> {
+        // update stored configuration
+        self.config = config;
+
+        // configure MEAS_RATE register
+        let meas_rate_value = ((config.resolution as u8) << 4) | (config.measurement_rate as u8);
+        self.write_register(Register::MeasRate, meas_rate_value).await?;
+
+        // configure GAIN register
+        self.write_register(Register::Gain, config.gain as u8).await?;
+
+        Ok(())
+    }
+
+    /// Configures the interrupt system
+    ///
+    /// Returns [`Error::InvalidConfig`] if `config` was not produced by
+    /// [`InterruptConfig::builder`] and carries an out-of-range threshold, a
+    /// persistence above 15, or a lower threshold above the upper one.
+    pub async fn configure_interrupt(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+        config.validate().map_err(|_| Error::InvalidConfig)?;
+
+        // update stored configuration
+        self.interrupt_config = config;
+
+        // configure INT_CFG register
+        let int_cfg_value = ((config.source as u8) << register::int_cfg::INT_SEL_SHIFT)
+            | ((config.mode as u8) << 3)
+            | ((config.enabled as u8) << 2);
+        self.write_register(Register::IntCfg, int_cfg_value).await?;
+
+        // configure INT_PERSISTENCE register
+        let int_persistence_value = config.persistence << register::int_persistence::PERSIST_SHIFT;
+        self.write_register(Register::IntPersistence, int_persistence_value).await?;
+
+        // configure upper threshold
+        self.write_register(Register::ThresUp0, (config.upper_threshold & 0xFF) as u8).await?;
+        self.write_register(Register::ThresUp1, ((config.upper_threshold >> 8) & 0xFF) as u8).await?;
+        self.write_register(Register::ThresUp2, ((config.upper_threshold >> 16) & 0x0F) as u8).await?;
+
+        // configure lower threshold
+        self.write_register(Register::ThresLow0, (config.lower_threshold & 0xFF) as u8).await?;
+        self.write_register(Register::ThresLow1, ((config.lower_threshold >> 8) & 0xFF) as u8).await?;
+        self.write_register(Register::ThresLow2, ((config.lower_threshold >> 16) & 0x0F) as u8).await?;
+
+        // configure variance threshold
+        self.write_register(Register::ThresVar, config.variance_threshold as u8).await?;
+
+        Ok(())
+    }
+
+    /// Reads the ALS data
+    pub async fn read_data(&mut self) -> Result<u32, Error<E>> {
+        let mut buffer = [0u8; 3];
+        self.read_registers(Register::Data0, &mut buffer).await?;
+
+        // combine the 3 bytes into a 20-bit value
+        let als_data = (buffer[0] as u32) | ((buffer[1] as u32) << 8) | ((buffer[2] as u32 & 0x0F) << 16);
+
+        Ok(als_data)
+    }
+
+    /// Reads the Clear data
+    pub async fn read_clear_data(&mut self) -> Result<u32, Error<E>> {
+        let mut buffer = [0u8; 3];
+        self.read_registers(Register::ClearData0, &mut buffer).await?;
+
+        // combine the 3 bytes into a 20-bit value
+        let clear_data = (buffer[0] as u32) | ((buffer[1] as u32) << 8) | ((buffer[2] as u32 & 0x0F) << 16);
+
+        Ok(clear_data)
+    }
+
+    /// Reads the ALS data along with whether the ADC has saturated at the configured
+    /// [`Resolution`](crate::Resolution)'s full-scale maximum
+    pub async fn read_data_sample(&mut self) -> Result<Sample, Error<E>> {
+        let value = self.read_data().await?;
+        Ok(Sample {
+            value,
+            saturated: value >= self.config.resolution.full_scale_max(),
+        })
+    }
+
+    /// Reads the Clear data along with whether the ADC has saturated at the configured
+    /// [`Resolution`](crate::Resolution)'s full-scale maximum
+    pub async fn read_clear_data_sample(&mut self) -> Result<Sample, Error<E>> {
+        let value = self.read_clear_data().await?;
+        Ok(Sample {
+            value,
+            saturated: value >= self.config.resolution.full_scale_max(),
+        })
+    }
+
+    /// Reads both ALS and Clear data in a single operation
+    pub async fn read_measurement_data(&mut self) -> Result<MeasurementData, Error<E>> {
+        let als = self.read_data().await?;
+        Ok(MeasurementData {
+            als,
+            clear: self.read_clear_data().await?,
+            #[cfg(feature = "out_f32")]
+            lux: self.counts_to_lux(als),
+        })
+    }
+
+    /// Reads the ALS data and converts it to illuminance in lux
+    ///
+    /// The conversion accounts for the currently configured [`Gain`](crate::Gain) and the
+    /// integration time implied by [`Resolution`](crate::Resolution), scaled by the device's
+    /// lux calibration factor (see [`Self::set_lux_factor`]).
+    #[cfg(feature = "out_f32")]
+    pub async fn read_lux(&mut self) -> Result<f32, Error<E>> {
+        let raw = self.read_data().await?;
+        Ok(self.counts_to_lux(raw))
+    }
+
+    /// Converts raw ALS counts to lux using the configured gain, integration
+    /// time and calibration factor
+    #[cfg(feature = "out_f32")]
+    fn counts_to_lux(&self, raw_counts: u32) -> f32 {
+        crate::types::counts_to_lux(raw_counts, self.config.gain, self.config.resolution, self.lux_factor)
+    }
+
+    /// Overrides the lux calibration factor, e.g. to compensate for
+    /// cover-glass attenuation
+    #[cfg(feature = "out_f32")]
+    pub fn set_lux_factor(&mut self, factor: f32) {
+        self.lux_factor = factor;
+    }
+
+    /// Gets the current lux calibration factor
+    #[cfg(feature = "out_f32")]
+    pub fn lux_factor(&self) -> f32 {
+        self.lux_factor
+    }
+
+    /// Waits for new ALS data to become ready and reads it
+    ///
+    /// Polls [`Self::is_data_ready`] at an interval sized to the configured
+    /// [`MeasurementRate`](crate::MeasurementRate), sleeping between polls with the provided
+    /// `D` instead of busy-spinning. Returns [`Error::Timeout`] if `DATA_STATUS` has not
+    /// asserted after `max_wait_us` microseconds.
+    pub async fn wait_for_data(&mut self, max_wait_us: u32) -> Result<MeasurementData, Error<E>> {
+        let poll_interval_us = (self.config.measurement_rate.period_us() / 8).max(1_000);
+        let mut waited_us = 0u32;
+
+        loop {
+            if self.is_data_ready().await? {
+                return self.read_measurement_data().await;
+            }
+
+            if waited_us >= max_wait_us {
+                return Err(Error::Timeout);
+            }
+
+            self.delay.delay_us(poll_interval_us).await;
+            waited_us = waited_us.saturating_add(poll_interval_us);
+        }
+    }
+
+    /// Reads the status register
+    pub async fn read_status(&mut self) -> Result<u8, Error<E>> {
+        self.read_register(Register::MainStatus).await
+    }
+
+    /// Reads and parses the status register into a Status struct
+    pub async fn get_status(&mut self) -> Result<Status, Error<E>> {
+        let status_reg = self.read_status().await?;
+
+        Ok(Status {
+            power_on: (status_reg & register::main_status::POWER_ON_STATUS) != 0,
+            interrupt: (status_reg & register::main_status::INT_STATUS) != 0,
+            data_ready: (status_reg & register::main_status::DATA_STATUS) != 0,
+        })
+    }
+
+    /// Checks if power-on status is set
+    pub async fn is_power_on_status(&mut self) -> Result<bool, Error<E>> {
+        let status = self.read_status().await?;
+        Ok((status & register::main_status::POWER_ON_STATUS) != 0)
+    }
+
+    /// Checks if ALS interrupt is triggered
+    pub async fn is_interrupt(&mut self) -> Result<bool, Error<E>> {
+        let status = self.read_status().await?;
+        Ok((status & register::main_status::INT_STATUS) != 0)
+    }
+
+    /// Checks if new ALS data is available
+    pub async fn is_data_ready(&mut self) -> Result<bool, Error<E>> {
+        let status = self.read_status().await?;
+        Ok((status & register::main_status::DATA_STATUS) != 0)
+    }
+
+    /// Gets the current ALS configuration
+    pub fn get_config(&self) -> Config {
+        self.config
+    }
+
+    /// Gets the current interrupt configuration
+    pub fn get_interrupt_config(&self) -> InterruptConfig {
+        self.interrupt_config
+    }
+
+    /// Translates a fired interrupt back into the source channel and mode that triggered it,
+    /// based on the currently configured [`InterruptConfig`]
+    pub fn interrupt_event(&self) -> InterruptEvent {
+        InterruptEvent {
+            source: self.interrupt_config.source,
+            mode: self.interrupt_config.mode,
+        }
+    }
+
+    /// Reads a single register
+    async fn read_register(&mut self, register: Register) -> Result<u8, Error<E>> {
+        let mut buffer = [0u8; 1];
+        self.i2c.write_read(I2C_ADDR, &[register as u8], &mut buffer).await?;
+        Ok(buffer[0])
+    }
+
+    /// Writes a value to a register
+    async fn write_register(&mut self, register: Register, value: u8) -> Result<(), Error<E>> {
+        self.i2c.write(I2C_ADDR, &[register as u8, value]).await?;
+        Ok(())
+    }
+
+    /// Reads multiple consecutive registers
+    async fn read_registers(&mut self, start_register: Register, buffer: &mut [u8]) -> Result<(), Error<E>> {
+        self.i2c.write_read(I2C_ADDR, &[start_register as u8], buffer).await?;
+        Ok(())
+    }
+
+    /// Releases the I2C interface
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C, D, IRQ, E> Apds9306<I2C, D, IRQ>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+    IRQ: Wait,
+{
+    /// Awaits the INT pin's (active-low) falling edge, then reads `MAIN_STATUS` to report which
+    /// condition fired and clears the latch (reading the status register already deasserts
+    /// `INT_STATUS`). Returns [`Error::Irq`] if the pin itself faults while waiting for the edge.
+    /// Use [`Self::interrupt_event`] to translate the fired condition back into its configured
+    /// [`InterruptSource`](crate::InterruptSource)/[`InterruptMode`](crate::InterruptMode).
+    pub async fn wait_for_interrupt(&mut self) -> Result<Status, Error<E, IRQ::Error>> {
+        self.irq.wait_for_falling_edge().await.map_err(Error::Irq)?;
+
+        match self.get_status().await {
+            Ok(status) => Ok(status),
+            Err(Error::I2c(e)) => Err(Error::I2c(e)),
+            Err(Error::InvalidConfig) => Err(Error::InvalidConfig),
+            Err(Error::DeviceNotFound) => Err(Error::DeviceNotFound),
+            Err(Error::Timeout) => Err(Error::Timeout),
+            Err(Error::Irq(never)) => match never {},
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use core::fmt;
 
+use crate::Error;
+
 /// Resolution/bit width settings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -24,6 +26,41 @@ impl Default for Resolution {
     }
 }
 
+#[cfg(feature = "out_f32")]
+impl Resolution {
+    /// Integration time implied by this resolution, in milliseconds.
+    pub fn integration_time_ms(self) -> f32 {
+        match self {
+            Self::Bits20 => 400.0,
+            Self::Bits19 => 200.0,
+            Self::Bits18 => 100.0,
+            Self::Bits17 => 50.0,
+            Self::Bits16 => 25.0,
+            Self::Bits13 => 3.125,
+        }
+    }
+}
+
+impl Resolution {
+    /// Bit width of the raw ADC count produced at this resolution
+    fn bits(self) -> u32 {
+        match self {
+            Self::Bits20 => 20,
+            Self::Bits19 => 19,
+            Self::Bits18 => 18,
+            Self::Bits17 => 17,
+            Self::Bits16 => 16,
+            Self::Bits13 => 13,
+        }
+    }
+
+    /// Maximum raw ADC count ("full scale") representable at this resolution.
+    /// A reading at or above this value means the ADC has railed.
+    pub fn full_scale_max(self) -> u32 {
+        (1u32 << self.bits()) - 1
+    }
+}
+
 /// Measurement rate settings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -50,6 +87,22 @@ impl Default for MeasurementRate {
     }
 }
 
+impl MeasurementRate {
+    /// Approximate interval, in microseconds, between the sensor producing
+    /// consecutive samples at this rate
+    pub fn period_us(self) -> u32 {
+        match self {
+            Self::Ms25 => 25_000,
+            Self::Ms50 => 50_000,
+            Self::Ms100 => 100_000,
+            Self::Ms200 => 200_000,
+            Self::Ms500 => 500_000,
+            Self::Ms1000 => 1_000_000,
+            Self::Ms2000 => 2_000_000,
+        }
+    }
+}
+
 /// Gain settings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -72,6 +125,27 @@ impl Default for Gain {
     }
 }
 
+#[cfg(feature = "out_f32")]
+impl Gain {
+    /// Numeric gain multiplier applied to the raw ADC counts.
+    pub fn value(self) -> f32 {
+        match self {
+            Self::Gain1 => 1.0,
+            Self::Gain3 => 3.0,
+            Self::Gain6 => 6.0,
+            Self::Gain9 => 9.0,
+            Self::Gain18 => 18.0,
+        }
+    }
+}
+
+/// Converts raw ADC counts to illuminance in lux, accounting for the configured gain and
+/// the integration time implied by `resolution`, scaled by the device's lux calibration factor
+#[cfg(feature = "out_f32")]
+pub(crate) fn counts_to_lux(raw_counts: u32, gain: Gain, resolution: Resolution, lux_factor: f32) -> f32 {
+    (raw_counts as f32 / gain.value()) * (100.0 / resolution.integration_time_ms()) * lux_factor
+}
+
 /// Interrupt source selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -153,6 +227,60 @@ impl Default for Config {
     }
 }
 
+impl Config {
+    /// Creates a builder for constructing a [`Config`]
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+/// Builder for [`Config`]
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigBuilder {
+    resolution: Resolution,
+    measurement_rate: MeasurementRate,
+    gain: Gain,
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self {
+            resolution: Resolution::default(),
+            measurement_rate: MeasurementRate::default(),
+            gain: Gain::default(),
+        }
+    }
+}
+
+impl ConfigBuilder {
+    /// Sets the resolution/bit width
+    pub fn resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Sets the measurement rate
+    pub fn measurement_rate(mut self, measurement_rate: MeasurementRate) -> Self {
+        self.measurement_rate = measurement_rate;
+        self
+    }
+
+    /// Sets the gain
+    pub fn gain(mut self, gain: Gain) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Builds the [`Config`]
+    pub fn build<E>(self) -> Result<Config, Error<E>> {
+        Ok(Config {
+            resolution: self.resolution,
+            measurement_rate: self.measurement_rate,
+            gain: self.gain,
+        })
+    }
+}
+
 /// Configuration for interrupts
 #[derive(Debug, Clone, Copy)]
 pub struct InterruptConfig {
@@ -186,6 +314,127 @@ impl Default for InterruptConfig {
     }
 }
 
+impl InterruptConfig {
+    /// Creates a builder for constructing a validated [`InterruptConfig`]
+    pub fn builder() -> InterruptConfigBuilder {
+        InterruptConfigBuilder::default()
+    }
+
+    /// Checks that both thresholds fit the 20-bit range, `persistence` is at most 15, and
+    /// `lower_threshold` does not exceed `upper_threshold`
+    pub(crate) fn validate(&self) -> Result<(), ()> {
+        if self.upper_threshold > 0x0FFFFF
+            || self.lower_threshold > 0x0FFFFF
+            || self.persistence > 15
+            || self.lower_threshold > self.upper_threshold
+        {
+            return Err(());
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for [`InterruptConfig`], validating thresholds and persistence at [`Self::build`]
+/// so that an invalid configuration is rejected before it reaches `configure_interrupt`
+#[derive(Debug, Clone, Copy)]
+pub struct InterruptConfigBuilder {
+    source: InterruptSource,
+    mode: InterruptMode,
+    enabled: bool,
+    persistence: u8,
+    upper_threshold: u32,
+    lower_threshold: u32,
+    variance_threshold: VarianceThreshold,
+}
+
+impl Default for InterruptConfigBuilder {
+    fn default() -> Self {
+        Self {
+            source: InterruptSource::default(),
+            mode: InterruptMode::default(),
+            enabled: false,
+            persistence: 0,
+            upper_threshold: 0x0FFFFF,
+            lower_threshold: 0,
+            variance_threshold: VarianceThreshold::default(),
+        }
+    }
+}
+
+impl InterruptConfigBuilder {
+    /// Sets the interrupt source
+    pub fn source(mut self, source: InterruptSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Sets the interrupt mode
+    pub fn mode(mut self, mode: InterruptMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Enables or disables the interrupt
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Sets the number of consecutive out-of-threshold measurements before an interrupt fires (0-15)
+    pub fn persistence(mut self, persistence: u8) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    /// Sets the upper threshold for interrupt (20-bit value)
+    pub fn upper_threshold(mut self, upper_threshold: u32) -> Self {
+        self.upper_threshold = upper_threshold;
+        self
+    }
+
+    /// Sets the lower threshold for interrupt (20-bit value)
+    pub fn lower_threshold(mut self, lower_threshold: u32) -> Self {
+        self.lower_threshold = lower_threshold;
+        self
+    }
+
+    /// Sets the variance threshold for variation mode
+    pub fn variance_threshold(mut self, variance_threshold: VarianceThreshold) -> Self {
+        self.variance_threshold = variance_threshold;
+        self
+    }
+
+    /// Builds the [`InterruptConfig`]
+    ///
+    /// Returns [`Error::InvalidConfig`] if either threshold exceeds the 20-bit range,
+    /// `persistence` exceeds 15, or `lower_threshold` is greater than `upper_threshold`.
+    pub fn build<E>(self) -> Result<InterruptConfig, Error<E>> {
+        let config = InterruptConfig {
+            source: self.source,
+            mode: self.mode,
+            enabled: self.enabled,
+            persistence: self.persistence,
+            upper_threshold: self.upper_threshold,
+            lower_threshold: self.lower_threshold,
+            variance_threshold: self.variance_threshold,
+        };
+
+        config.validate().map_err(|_| Error::InvalidConfig)?;
+
+        Ok(config)
+    }
+}
+
+/// Describes which condition triggered a fired ALS interrupt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InterruptEvent {
+    /// Channel that was being monitored (Clear or ALS)
+    pub source: InterruptSource,
+    /// Whether the interrupt was configured for threshold or variation mode
+    pub mode: InterruptMode,
+}
+
 /// Status information from the APDS-9306
 #[derive(Debug, Clone, Copy)]
 pub struct Status {
@@ -218,6 +467,41 @@ impl defmt::Format for Status {
     }
 }
 
+/// A raw ADC reading paired with a saturation flag
+///
+/// `saturated` is set when `value` has reached the resolution-dependent full-scale
+/// maximum (see [`Resolution::full_scale_max`]), meaning the ADC has railed and the
+/// reading no longer reflects the actual illuminance. Autoranging logic should step
+/// `Gain`/`Resolution` down when this is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    /// Raw ADC count
+    pub value: u32,
+    /// Set when `value` has reached the full-scale maximum for the active resolution
+    pub saturated: bool,
+}
+
+impl fmt::Display for Sample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Sample {{ value: {}, saturated: {} }}",
+            self.value, self.saturated
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Sample {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "Sample {{ value: {}, saturated: {} }}",
+            self.value, self.saturated
+        );
+    }
+}
+
 /// Measurement data from the APDS-9306
 #[derive(Debug, Clone, Copy)]
 pub struct MeasurementData {
@@ -225,8 +509,24 @@ pub struct MeasurementData {
     pub als: u32,
     /// Clear channel data
     pub clear: u32,
+    /// ALS channel data converted to lux, using the configured gain,
+    /// integration time and calibration factor
+    #[cfg(feature = "out_f32")]
+    pub lux: f32,
+}
+
+#[cfg(feature = "out_f32")]
+impl fmt::Display for MeasurementData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MeasurementData {{ als: {}, clear: {}, lux: {} }}",
+            self.als, self.clear, self.lux
+        )
+    }
 }
 
+#[cfg(not(feature = "out_f32"))]
 impl fmt::Display for MeasurementData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -237,7 +537,18 @@ impl fmt::Display for MeasurementData {
     }
 }
 
-#[cfg(feature = "defmt")]
+#[cfg(all(feature = "defmt", feature = "out_f32"))]
+impl defmt::Format for MeasurementData {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "MeasurementData {{ als: {}, clear: {}, lux: {} }}",
+            self.als, self.clear, self.lux
+        );
+    }
+}
+
+#[cfg(all(feature = "defmt", not(feature = "out_f32")))]
 impl defmt::Format for MeasurementData {
     fn format(&self, fmt: defmt::Formatter) {
         defmt::write!(
@@ -247,3 +558,59 @@ impl defmt::Format for MeasurementData {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_scale_max_matches_bit_width() {
+        assert_eq!(Resolution::Bits20.full_scale_max(), 0xF_FFFF);
+        assert_eq!(Resolution::Bits18.full_scale_max(), 0x3_FFFF);
+        assert_eq!(Resolution::Bits13.full_scale_max(), 0x1FFF);
+    }
+
+    #[test]
+    #[cfg(feature = "out_f32")]
+    fn counts_to_lux_applies_gain_integration_time_and_factor() {
+        // 18-bit resolution (100ms) at Gain3, raw count of 300 with a factor of 1.0 should
+        // come out to 100 lux: (300 / 3) * (100 / 100) * 1.0
+        let lux = counts_to_lux(300, Gain::Gain3, Resolution::Bits18, 1.0);
+        assert!((lux - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn interrupt_config_builder_rejects_persistence_above_15() {
+        let result = InterruptConfigBuilder::default()
+            .persistence(16)
+            .build::<()>();
+        assert!(matches!(result, Err(Error::InvalidConfig)));
+    }
+
+    #[test]
+    fn interrupt_config_builder_rejects_threshold_above_20_bits() {
+        let result = InterruptConfigBuilder::default()
+            .upper_threshold(0x10_0000)
+            .build::<()>();
+        assert!(matches!(result, Err(Error::InvalidConfig)));
+    }
+
+    #[test]
+    fn interrupt_config_builder_rejects_lower_above_upper() {
+        let result = InterruptConfigBuilder::default()
+            .lower_threshold(100)
+            .upper_threshold(50)
+            .build::<()>();
+        assert!(matches!(result, Err(Error::InvalidConfig)));
+    }
+
+    #[test]
+    fn interrupt_config_builder_accepts_valid_config() {
+        let result = InterruptConfigBuilder::default()
+            .persistence(15)
+            .lower_threshold(10)
+            .upper_threshold(20)
+            .build::<()>();
+        assert!(result.is_ok());
+    }
+}